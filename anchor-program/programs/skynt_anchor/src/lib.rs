@@ -1,5 +1,17 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+// Opt-in state-compressed ledger for `PowChallenge` (see `create_challenge_compressed` /
+// `submit_challenge_solution_compressed`) — same concurrent-Merkle-tree approach spl-account-
+// compression uses for compressed NFTs, applied here to solution records instead of leaves.
+use spl_account_compression::{
+    cpi::{
+        accounts::{Initialize, Modify},
+        append, init_empty_merkle_tree,
+    },
+    program::SplAccountCompression,
+    Noop,
+};
 
 pub mod pow;
 pub mod difficulty;
@@ -12,13 +24,123 @@ use mint::*;
 // Replace this with the actual program ID after running: anchor keys list
 declare_id!("11111111111111111111111111111111");
 
+// A challenge's `recent_blockhash` must appear within this many of the most
+// recent entries of `SlotHashes` (index 0 = most recent slot). Anything older
+// is rejected as stale so a challenge can't be anchored to a blockhash that's
+// about to roll out of the sysvar.
+const MAX_BLOCKHASH_AGE_SLOTS: usize = 150;
+
 // Space constants for account sizing
-// PowChallenge: discriminator(8) + authority(32) + seed(32) + difficulty_target(16) +
-//               expires_at(8) + solutions_count(4) + status(1) + padding(3) = 104
-const POW_CHALLENGE_SPACE: usize = 8 + 32 + 32 + 16 + 8 + 4 + 1 + 3;
+// PowChallenge: discriminator(8) + authority(32) + seed(32) + recent_blockhash(32) +
+//               difficulty_target(16) + expires_at(8) + solutions_count(4) + status(1) +
+//               compressed(1) + merkle_tree(32) + padding(2) = 168
+const POW_CHALLENGE_SPACE: usize = 8 + 32 + 32 + 32 + 16 + 8 + 4 + 1 + 1 + 32 + 2;
 // SolutionRecord: discriminator(8) + challenge(32) + miner(32) + nonce(8) +
 //                 pow_hash(32) + submitted_at(8) = 120
 const SOLUTION_RECORD_SPACE: usize = 8 + 32 + 32 + 8 + 32 + 8;
+// SolutionCommitment: discriminator(8) + challenge(32) + miner(32) + commitment(32) +
+//                      committed_at(8) + commit_slot(8) = 120
+const SOLUTION_COMMITMENT_SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8;
+
+// A revealed solution must be submitted at least this many slots after its commitment
+// landed, so the commitment can't be revealed in the same block it was made.
+const MIN_REVEAL_DELAY_SLOTS: u64 = 1;
+
+// StorageChallenge: discriminator(8) + authority(32) + data_root(32) + recent_blockhash(32) +
+//                   total_chunks(8) + sample_count(1) + difficulty_target(16) + expires_at(8) +
+//                   proofs_count(4) + status(1) + padding(3) = 145
+const STORAGE_CHALLENGE_SPACE: usize = 8 + 32 + 32 + 32 + 8 + 1 + 16 + 8 + 4 + 1 + 3;
+// StorageProofRecord: discriminator(8) + challenge(32) + miner(32) + result_hash(32) +
+//                      accepted_at(8) = 112
+const STORAGE_PROOF_RECORD_SPACE: usize = 8 + 32 + 32 + 32 + 8;
+// All dataset chunks sampled by a storage challenge must be exactly this many bytes.
+const STORAGE_CHUNK_SIZE: usize = 256;
+// Upper bound on how many chunks a single proof can sample, so a submission's compute/tx
+// size stays bounded regardless of what an authority configures.
+const MAX_SAMPLE_COUNT: u8 = 16;
+
+// CompressedSolutionMarker: discriminator(8) only — `append` has no membership check of its
+// own, so this PDA's existence (one per challenge/miner, enforced by `init`) is the replay
+// guard for the compressed solution path. This is a known, deliberate trade-off, not a full
+// realization of "no per-miner account": a root-verified claimed-leaf / non-membership proof
+// would avoid allocating anything per miner, but needs a concurrent Merkle tree root this
+// program can read back and verify against, which `PowChallenge` does not track (see its
+// `merkle_tree` field doc). Until that's in place, this PDA is the smallest rent-paying
+// account the repo's replay-guard idiom can produce — 8 bytes versus `SolutionRecord`'s 120 —
+// so the compressed path still allocates one (small) account per miner.
+const COMPRESSED_SOLUTION_MARKER_SPACE: usize = 8;
+
+/// Deterministically derives the dataset chunk indices a miner must prove for a storage
+/// challenge: `hashv(&[data_root, recent_blockhash, miner_pubkey])` expanded into
+/// `sample_count` indices via successive hashing.
+fn derive_chunk_indices(
+    data_root: &[u8; 32],
+    recent_blockhash: &[u8; 32],
+    miner: &Pubkey,
+    sample_count: u8,
+    total_chunks: u64,
+) -> Vec<u64> {
+    let mut indices = Vec::with_capacity(sample_count as usize);
+    let mut seed = hashv(&[data_root, recent_blockhash, miner.as_ref()]).to_bytes();
+    for _ in 0..sample_count {
+        let offset = u64::from_le_bytes(seed[0..8].try_into().unwrap());
+        indices.push(offset % total_chunks);
+        seed = hashv(&[&seed]).to_bytes();
+    }
+    indices
+}
+
+/// Verifies a Merkle inclusion proof for `leaf` at `index` against `root`, folding in
+/// siblings bottom-up (sibling order determined by the bit of `index` at each level).
+fn verify_merkle_proof(leaf: [u8; 32], index: u64, proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let mut computed = leaf;
+    let mut idx = index;
+    for sibling in proof {
+        computed = if idx % 2 == 0 {
+            hashv(&[&computed, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &computed]).to_bytes()
+        };
+        idx /= 2;
+    }
+    computed == *root
+}
+
+// Byte layout of the SlotHashes sysvar: an 8-byte little-endian entry count, followed by
+// that many (8-byte LE slot, 32-byte hash) entries, ordered most-recent-first.
+const SLOT_HASHES_ENTRY_SIZE: usize = 8 + 32;
+
+/// Confirms `blockhash` is a genuine, recent entry of the `SlotHashes` sysvar.
+/// Used to anchor challenge seeds so a miner can't grind solutions before the
+/// anchoring block existed (mirrors `BlockhashQuery::Source`'s freshness check).
+///
+/// Scans the sysvar's raw bytes directly instead of going through
+/// `SlotHashes::from_account_info`, which bincode-deserializes the entire (up to ~20 KB,
+/// 512-entry) sysvar into a heap-allocated `Vec` on every call — unnecessary compute for a
+/// lookup that only ever needs to look at the first `MAX_BLOCKHASH_AGE_SLOTS` entries.
+fn verify_recent_blockhash(slot_hashes_info: &AccountInfo, blockhash: &[u8; 32]) -> Result<()> {
+    require_keys_eq!(*slot_hashes_info.key, slot_hashes::ID, ErrorCode::StaleBlockhash);
+
+    let data = slot_hashes_info
+        .try_borrow_data()
+        .map_err(|_| error!(ErrorCode::StaleBlockhash))?;
+    require!(data.len() >= 8, ErrorCode::StaleBlockhash);
+    let entry_count = u64::from_le_bytes(data[0..8].try_into().unwrap()) as usize;
+    let scan_count = entry_count.min(MAX_BLOCKHASH_AGE_SLOTS);
+
+    for i in 0..scan_count {
+        let entry_start = 8 + i * SLOT_HASHES_ENTRY_SIZE;
+        let hash_start = entry_start + 8;
+        let hash_end = hash_start + 32;
+        if hash_end > data.len() {
+            break;
+        }
+        if &data[hash_start..hash_end] == blockhash {
+            return Ok(());
+        }
+    }
+    Err(error!(ErrorCode::StaleBlockhash))
+}
 
 #[program]
 pub mod skynt_anchor {
@@ -45,27 +167,35 @@ pub mod skynt_anchor {
 
     /// Admin creates a new PoW challenge with a unique seed, difficulty target, and expiration.
     /// The challenge seed is stored on-chain so miners can fetch and work against it.
+    /// `recent_blockhash` must be a genuinely recent entry of `SlotHashes` so the seed is
+    /// anchored to a block that didn't exist yet when a miner could have started grinding.
     pub fn create_challenge(
         ctx: Context<CreateChallenge>,
         seed: [u8; 32],
+        recent_blockhash: [u8; 32],
         difficulty_target: u128,
         expires_at: i64,
     ) -> Result<()> {
         let now = Clock::get()?.unix_timestamp;
         require!(expires_at > now, ErrorCode::ChallengeAlreadyExpired);
         require!(difficulty_target > 0, ErrorCode::InvalidDifficulty);
+        verify_recent_blockhash(&ctx.accounts.slot_hashes, &recent_blockhash)?;
 
         let challenge = &mut ctx.accounts.challenge;
         challenge.authority = ctx.accounts.authority.key();
         challenge.seed = seed;
+        challenge.recent_blockhash = recent_blockhash;
         challenge.difficulty_target = difficulty_target;
         challenge.expires_at = expires_at;
         challenge.solutions_count = 0;
         challenge.status = ChallengeStatus::Active;
+        challenge.compressed = false;
+        challenge.merkle_tree = Pubkey::default();
 
         msg!(
-            "Challenge created: seed={} difficulty={} expires_at={}",
+            "Challenge created: seed={} blockhash={} difficulty={} expires_at={}",
             hex_encode_8(&seed),
+            hex_encode_8(&recent_blockhash),
             difficulty_target,
             expires_at
         );
@@ -75,6 +205,8 @@ pub mod skynt_anchor {
     /// Miner submits a PoW solution for a challenge.
     /// Uses a PDA seeded by [challenge, miner] to enforce one solution record per miner per challenge
     /// (replay protection). On-chain SHA-256 verification via solana_program::hash.
+    /// Only valid for non-compressed challenges — a compressed challenge's solutions belong
+    /// in its Merkle tree via `submit_challenge_solution_compressed`, not a `SolutionRecord` PDA.
     pub fn submit_challenge_solution(
         ctx: Context<SubmitChallengeSolution>,
         nonce: u64,
@@ -82,16 +214,20 @@ pub mod skynt_anchor {
         let challenge = &mut ctx.accounts.challenge;
         let now = Clock::get()?.unix_timestamp;
 
+        require!(!challenge.compressed, ErrorCode::ChallengeIsCompressed);
         require!(
             challenge.status == ChallengeStatus::Active,
             ErrorCode::ChallengeNotActive
         );
         require!(now <= challenge.expires_at, ErrorCode::ChallengeAlreadyExpired);
 
-        // Compute SHA-256: hash(seed || nonce_le || miner_pubkey)
+        // Compute SHA-256: hash(seed || recent_blockhash || nonce_le || miner_pubkey).
+        // Binding the challenge's anchoring blockhash into the hash means no nonce can
+        // have been precomputed before that block existed.
         let nonce_bytes = nonce.to_le_bytes();
         let pow_hash_result = hashv(&[
             &challenge.seed,
+            &challenge.recent_blockhash,
             &nonce_bytes,
             ctx.accounts.miner.key().as_ref(),
         ]);
@@ -123,6 +259,362 @@ pub mod skynt_anchor {
         );
         Ok(())
     }
+
+    /// Commits to a solution without revealing it, so the winning nonce can't be copied out
+    /// of the mempool and resubmitted by someone else before the original miner lands.
+    /// `commitment` must be `hashv(&[nonce_le, miner_pubkey, salt])`, computed off-chain.
+    /// Only valid for non-compressed challenges — same restriction as `submit_challenge_solution`,
+    /// since `reveal_solution` ends in the same `SolutionRecord` bookkeeping.
+    pub fn commit_solution(ctx: Context<CommitSolution>, commitment: [u8; 32]) -> Result<()> {
+        let challenge = &ctx.accounts.challenge;
+        let now = Clock::get()?;
+
+        require!(!challenge.compressed, ErrorCode::ChallengeIsCompressed);
+        require!(
+            challenge.status == ChallengeStatus::Active,
+            ErrorCode::ChallengeNotActive
+        );
+        require!(
+            now.unix_timestamp <= challenge.expires_at,
+            ErrorCode::ChallengeAlreadyExpired
+        );
+
+        let solution_commitment = &mut ctx.accounts.solution_commitment;
+        solution_commitment.challenge = challenge.key();
+        solution_commitment.miner = ctx.accounts.miner.key();
+        solution_commitment.commitment = commitment;
+        solution_commitment.committed_at = now.unix_timestamp;
+        solution_commitment.commit_slot = now.slot;
+
+        msg!(
+            "Solution committed: miner={} commitment={}",
+            ctx.accounts.miner.key(),
+            hex_encode_8(&commitment)
+        );
+        Ok(())
+    }
+
+    /// Reveals a previously committed solution. Recomputes the commitment from `nonce` and
+    /// `salt` and checks it matches, then runs the same difficulty check and `SolutionRecord`
+    /// bookkeeping as `submit_challenge_solution`. Because the commitment is bound to the
+    /// miner's pubkey, observing it in the mempool doesn't let anyone else steal the reveal.
+    /// Only valid for non-compressed challenges — it allocates the same `SolutionRecord` PDA
+    /// `submit_challenge_solution` does, which would double-count `solutions_count` alongside
+    /// `submit_challenge_solution_compressed`'s leaf/marker bookkeeping on a compressed challenge.
+    pub fn reveal_solution(
+        ctx: Context<RevealSolution>,
+        nonce: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let now = Clock::get()?;
+        let miner = ctx.accounts.miner.key();
+
+        require!(!challenge.compressed, ErrorCode::ChallengeIsCompressed);
+        require!(
+            challenge.status == ChallengeStatus::Active,
+            ErrorCode::ChallengeNotActive
+        );
+        require!(
+            now.unix_timestamp <= challenge.expires_at,
+            ErrorCode::ChallengeAlreadyExpired
+        );
+
+        let solution_commitment = &ctx.accounts.solution_commitment;
+        require!(
+            now.slot >= solution_commitment.commit_slot + MIN_REVEAL_DELAY_SLOTS,
+            ErrorCode::RevealTooEarly
+        );
+
+        let nonce_bytes = nonce.to_le_bytes();
+        let recomputed = hashv(&[&nonce_bytes, miner.as_ref(), &salt]).to_bytes();
+        require!(
+            recomputed == solution_commitment.commitment,
+            ErrorCode::CommitmentMismatch
+        );
+
+        let pow_hash_result = hashv(&[
+            &challenge.seed,
+            &challenge.recent_blockhash,
+            &nonce_bytes,
+            miner.as_ref(),
+        ]);
+        let pow_hash: [u8; 32] = pow_hash_result.to_bytes();
+
+        let hash_num = u128::from_be_bytes(pow_hash[0..16].try_into().unwrap());
+        require!(
+            hash_num < challenge.difficulty_target,
+            ErrorCode::InvalidProofOfWork
+        );
+
+        let record = &mut ctx.accounts.solution_record;
+        record.challenge = challenge.key();
+        record.miner = miner;
+        record.nonce = nonce;
+        record.pow_hash = pow_hash;
+        record.submitted_at = now.unix_timestamp;
+
+        challenge.solutions_count = challenge.solutions_count.saturating_add(1);
+
+        msg!(
+            "Committed solution revealed: miner={} nonce={} hash={} solutions_total={}",
+            miner,
+            nonce,
+            hex_encode_8(&pow_hash),
+            challenge.solutions_count
+        );
+        Ok(())
+    }
+
+    /// Admin creates a Proof-of-Replication storage challenge: miners earn a mint by proving
+    /// they store sampled chunks of a published dataset, rather than by grinding SHA hashes.
+    /// `data_root` is the Merkle root over `total_chunks` fixed-size (`STORAGE_CHUNK_SIZE`)
+    /// chunks. `recent_blockhash` is anchored and verified the same way as `create_challenge`.
+    pub fn create_storage_challenge(
+        ctx: Context<CreateStorageChallenge>,
+        data_root: [u8; 32],
+        recent_blockhash: [u8; 32],
+        total_chunks: u64,
+        sample_count: u8,
+        difficulty_target: u128,
+        expires_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at > now, ErrorCode::ChallengeAlreadyExpired);
+        require!(difficulty_target > 0, ErrorCode::InvalidDifficulty);
+        require!(total_chunks > 0, ErrorCode::InvalidSampleCount);
+        require!(
+            sample_count > 0
+                && sample_count <= MAX_SAMPLE_COUNT
+                && (sample_count as u64) <= total_chunks,
+            ErrorCode::InvalidSampleCount
+        );
+        verify_recent_blockhash(&ctx.accounts.slot_hashes, &recent_blockhash)?;
+
+        let challenge = &mut ctx.accounts.challenge;
+        challenge.authority = ctx.accounts.authority.key();
+        challenge.data_root = data_root;
+        challenge.recent_blockhash = recent_blockhash;
+        challenge.total_chunks = total_chunks;
+        challenge.sample_count = sample_count;
+        challenge.difficulty_target = difficulty_target;
+        challenge.expires_at = expires_at;
+        challenge.proofs_count = 0;
+        challenge.status = ChallengeStatus::Active;
+
+        msg!(
+            "Storage challenge created: data_root={} total_chunks={} sample_count={} difficulty={} expires_at={}",
+            hex_encode_8(&data_root),
+            total_chunks,
+            sample_count,
+            difficulty_target,
+            expires_at
+        );
+        Ok(())
+    }
+
+    /// Miner submits a Proof-of-Replication proof: the required chunk indices are derived
+    /// deterministically from the challenge and the miner's own pubkey, so the miner must
+    /// supply exactly those chunks (plus Merkle inclusion proofs) to be accepted.
+    pub fn submit_storage_proof(
+        ctx: Context<SubmitStorageProof>,
+        chunks: Vec<Vec<u8>>,
+        proofs: Vec<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let now = Clock::get()?.unix_timestamp;
+        let miner = ctx.accounts.miner.key();
+
+        require!(
+            challenge.status == ChallengeStatus::Active,
+            ErrorCode::ChallengeNotActive
+        );
+        require!(now <= challenge.expires_at, ErrorCode::ChallengeAlreadyExpired);
+        require!(
+            chunks.len() == challenge.sample_count as usize
+                && proofs.len() == challenge.sample_count as usize,
+            ErrorCode::SampleLengthMismatch
+        );
+
+        let indices = derive_chunk_indices(
+            &challenge.data_root,
+            &challenge.recent_blockhash,
+            &miner,
+            challenge.sample_count,
+            challenge.total_chunks,
+        );
+
+        let mut chunk_refs: Vec<&[u8]> = Vec::with_capacity(chunks.len());
+        for ((chunk, proof), index) in chunks.iter().zip(proofs.iter()).zip(indices.iter()) {
+            require!(chunk.len() == STORAGE_CHUNK_SIZE, ErrorCode::SampleLengthMismatch);
+            let leaf = hashv(&[chunk.as_slice()]).to_bytes();
+            require!(
+                verify_merkle_proof(leaf, *index, proof, &challenge.data_root),
+                ErrorCode::InvalidMerkleProof
+            );
+            chunk_refs.push(chunk.as_slice());
+        }
+
+        // Fold every sampled chunk into one result hash and check it meets the difficulty
+        // target, the same way a PoW hash is checked.
+        let result_hash = hashv(&chunk_refs).to_bytes();
+        let hash_num = u128::from_be_bytes(result_hash[0..16].try_into().unwrap());
+        require!(
+            hash_num < challenge.difficulty_target,
+            ErrorCode::InvalidProofOfWork
+        );
+
+        let record = &mut ctx.accounts.storage_proof_record;
+        record.challenge = challenge.key();
+        record.miner = miner;
+        record.result_hash = result_hash;
+        record.accepted_at = now;
+
+        challenge.proofs_count = challenge.proofs_count.saturating_add(1);
+
+        msg!(
+            "Storage proof accepted: miner={} result_hash={} proofs_total={}",
+            miner,
+            hex_encode_8(&result_hash),
+            challenge.proofs_count
+        );
+        Ok(())
+    }
+
+    /// Admin creates a new PoW challenge in compressed mode: accepted solutions are appended
+    /// as leaves to a `spl-account-compression` concurrent Merkle tree instead of each minting
+    /// a rent-paying `SolutionRecord` PDA, so challenges with thousands of winners stay cheap.
+    /// `merkle_tree` must already be allocated (correctly sized for `max_depth`/`max_buffer_size`)
+    /// by the caller, per `spl-account-compression`'s own account-creation convention.
+    /// `PowChallenge` only stores the tree's address, not its root: the root changes on every
+    /// accepted solution, so the tree account itself (and the changelog the compression
+    /// program logs via `log_wrapper` on every `init`/`append`) is the only source of truth
+    /// indexers should read from — caching a copy here would just go stale between calls.
+    pub fn create_challenge_compressed(
+        ctx: Context<CreateChallengeCompressed>,
+        seed: [u8; 32],
+        recent_blockhash: [u8; 32],
+        difficulty_target: u128,
+        expires_at: i64,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at > now, ErrorCode::ChallengeAlreadyExpired);
+        require!(difficulty_target > 0, ErrorCode::InvalidDifficulty);
+        verify_recent_blockhash(&ctx.accounts.slot_hashes, &recent_blockhash)?;
+
+        let merkle_tree_key = ctx.accounts.merkle_tree.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[merkle_tree_key.as_ref(), &[ctx.bumps.tree_authority]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.compression_program.to_account_info(),
+            Initialize {
+                authority: ctx.accounts.tree_authority.to_account_info(),
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                noop: ctx.accounts.log_wrapper.to_account_info(),
+            },
+            signer_seeds,
+        );
+        init_empty_merkle_tree(cpi_ctx, max_depth, max_buffer_size)?;
+
+        let challenge = &mut ctx.accounts.challenge;
+        challenge.authority = ctx.accounts.authority.key();
+        challenge.seed = seed;
+        challenge.recent_blockhash = recent_blockhash;
+        challenge.difficulty_target = difficulty_target;
+        challenge.expires_at = expires_at;
+        challenge.solutions_count = 0;
+        challenge.status = ChallengeStatus::Active;
+        challenge.compressed = true;
+        challenge.merkle_tree = ctx.accounts.merkle_tree.key();
+
+        msg!(
+            "Compressed challenge created: seed={} blockhash={} difficulty={} expires_at={} merkle_tree={}",
+            hex_encode_8(&seed),
+            hex_encode_8(&recent_blockhash),
+            difficulty_target,
+            expires_at,
+            ctx.accounts.merkle_tree.key()
+        );
+        Ok(())
+    }
+
+    /// Miner submits a PoW solution against a compressed challenge. Identical hash/difficulty
+    /// check to `submit_challenge_solution`, but instead of an `init`'d `SolutionRecord` PDA
+    /// holding the full solution, the solution is appended as a leaf to the challenge's Merkle
+    /// tree — the compression program's own CPI into `log_wrapper` is what emits the leaf and
+    /// the tree's new root, so indexers can reconstruct the full solution set off-chain without
+    /// `PowChallenge` needing to cache a (necessarily-stale-between-calls) copy of the root.
+    /// `append` itself performs no membership check, so replay protection is a small
+    /// `CompressedSolutionMarker` PDA, unique per (challenge, miner) via `init`, exactly like
+    /// `SolutionRecord` provides for the non-compressed path. Note this is a rent-cost
+    /// trade-off, not the full "no per-miner account" property a root-verified claimed-leaf
+    /// proof would give — see `CompressedSolutionMarker`'s doc comment.
+    pub fn submit_challenge_solution_compressed(
+        ctx: Context<SubmitChallengeSolutionCompressed>,
+        nonce: u64,
+    ) -> Result<()> {
+        let challenge = &mut ctx.accounts.challenge;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(challenge.compressed, ErrorCode::ChallengeNotCompressed);
+        require!(
+            challenge.status == ChallengeStatus::Active,
+            ErrorCode::ChallengeNotActive
+        );
+        require!(now <= challenge.expires_at, ErrorCode::ChallengeAlreadyExpired);
+        require_keys_eq!(
+            ctx.accounts.merkle_tree.key(),
+            challenge.merkle_tree,
+            ErrorCode::MerkleTreeMismatch
+        );
+        // `solution_marker` is declared `init` in the accounts struct, so re-submitting for
+        // a (challenge, miner) pair that already has an accepted leaf fails before we even
+        // reach this handler.
+
+        let nonce_bytes = nonce.to_le_bytes();
+        let miner = ctx.accounts.miner.key();
+        let pow_hash_result = hashv(&[
+            &challenge.seed,
+            &challenge.recent_blockhash,
+            &nonce_bytes,
+            miner.as_ref(),
+        ]);
+        let pow_hash: [u8; 32] = pow_hash_result.to_bytes();
+
+        let hash_num = u128::from_be_bytes(pow_hash[0..16].try_into().unwrap());
+        require!(
+            hash_num < challenge.difficulty_target,
+            ErrorCode::InvalidProofOfWork
+        );
+
+        let submitted_at_bytes = now.to_le_bytes();
+        let leaf = hashv(&[miner.as_ref(), &nonce_bytes, &pow_hash, &submitted_at_bytes]).to_bytes();
+
+        let merkle_tree_key = ctx.accounts.merkle_tree.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[merkle_tree_key.as_ref(), &[ctx.bumps.tree_authority]]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.compression_program.to_account_info(),
+            Modify {
+                authority: ctx.accounts.tree_authority.to_account_info(),
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                noop: ctx.accounts.log_wrapper.to_account_info(),
+            },
+            signer_seeds,
+        );
+        append(cpi_ctx, leaf)?;
+
+        challenge.solutions_count = challenge.solutions_count.saturating_add(1);
+
+        msg!(
+            "Compressed challenge solution accepted: miner={} nonce={} leaf={} solutions_total={}",
+            miner,
+            nonce,
+            hex_encode_8(&leaf),
+            challenge.solutions_count
+        );
+        Ok(())
+    }
 }
 
 /// Encodes the first 8 bytes of a slice as a hex string for logging (abbreviated).
@@ -155,6 +647,8 @@ pub struct PowChallenge {
     pub authority: Pubkey,
     /// 32-byte challenge seed published to miners.
     pub seed: [u8; 32],
+    /// `SlotHashes` blockhash the challenge was anchored to at creation time.
+    pub recent_blockhash: [u8; 32],
     /// Difficulty target — hash_num (first 16 bytes, big-endian u128) must be < this value.
     pub difficulty_target: u128,
     /// Unix timestamp after which no new solutions are accepted.
@@ -163,6 +657,15 @@ pub struct PowChallenge {
     pub solutions_count: u32,
     /// Current lifecycle status.
     pub status: ChallengeStatus,
+    /// When true, accepted solutions are appended as leaves to `merkle_tree` instead of
+    /// spawning a `SolutionRecord` PDA per miner. Set once at `create_challenge_compressed`
+    /// time and immutable afterward.
+    pub compressed: bool,
+    /// The `spl-account-compression` concurrent Merkle tree account backing this challenge's
+    /// solution ledger. `Pubkey::default()` when `compressed` is false. Holds the
+    /// authoritative current root — not duplicated here, since it changes on every accepted
+    /// solution and the compression program's own changelog is the source of truth for it.
+    pub merkle_tree: Pubkey,
 }
 
 /// Lifecycle status for a PoW challenge.
@@ -189,6 +692,72 @@ pub struct SolutionRecord {
     pub submitted_at: i64,
 }
 
+/// Per-miner commit phase of the commit–reveal flow (PDA: seeds = [b"commitment",
+/// challenge_key, miner_key]). Closed on reveal — rent is refunded to the miner.
+#[account]
+pub struct SolutionCommitment {
+    /// The challenge this commitment targets.
+    pub challenge: Pubkey,
+    /// The miner's public key (binds the commitment so it can't be reused by anyone else).
+    pub miner: Pubkey,
+    /// `hashv(&[nonce_le, miner_pubkey, salt])`, checked against the reveal.
+    pub commitment: [u8; 32],
+    /// Unix timestamp the commitment was recorded at.
+    pub committed_at: i64,
+    /// Slot the commitment was recorded at — reveals must land at least
+    /// `MIN_REVEAL_DELAY_SLOTS` after this.
+    pub commit_slot: u64,
+}
+
+/// On-chain record of a Proof-of-Replication storage challenge posted by an authority.
+#[account]
+pub struct StorageChallenge {
+    /// The account that created this challenge (admin/authority).
+    pub authority: Pubkey,
+    /// Merkle root over `total_chunks` fixed-size (`STORAGE_CHUNK_SIZE`) chunks of the
+    /// published dataset.
+    pub data_root: [u8; 32],
+    /// `SlotHashes` blockhash the challenge was anchored to at creation time.
+    pub recent_blockhash: [u8; 32],
+    /// Total number of chunks in the published dataset.
+    pub total_chunks: u64,
+    /// Number of chunks a miner must sample and prove per submission.
+    pub sample_count: u8,
+    /// Difficulty target — the folded result hash (first 16 bytes, big-endian u128) must
+    /// be < this value.
+    pub difficulty_target: u128,
+    /// Unix timestamp after which no new proofs are accepted.
+    pub expires_at: i64,
+    /// Number of valid proofs submitted so far.
+    pub proofs_count: u32,
+    /// Current lifecycle status.
+    pub status: ChallengeStatus,
+}
+
+/// Per-miner storage proof record (PDA: seeds = [b"storage-proof", challenge_key, miner_key]).
+/// One record per (challenge, miner) pair — provides replay protection.
+#[account]
+pub struct StorageProofRecord {
+    /// The challenge this proof targets.
+    pub challenge: Pubkey,
+    /// The miner's public key.
+    pub miner: Pubkey,
+    /// Folded hash of the sampled chunks that satisfied the difficulty.
+    pub result_hash: [u8; 32],
+    /// Acceptance timestamp.
+    pub accepted_at: i64,
+}
+
+/// Replay guard for the compressed solution path (PDA: seeds = [b"compressed-solution",
+/// challenge_key, miner_key]). Holds no data — its mere existence (enforced via `init`,
+/// which fails if the PDA is already allocated) is what `append` itself doesn't check.
+///
+/// This is a smaller rent-paying account standing in for a root-verified claimed-leaf /
+/// non-membership proof, not a true elimination of per-miner accounts on the compressed path —
+/// see the `COMPRESSED_SOLUTION_MARKER_SPACE` comment for why.
+#[account]
+pub struct CompressedSolutionMarker {}
+
 // ─── Error codes ─────────────────────────────────────────────────────────────
 
 #[error_code]
@@ -201,6 +770,24 @@ pub enum ErrorCode {
     ChallengeAlreadyExpired,
     #[msg("Difficulty target must be greater than zero")]
     InvalidDifficulty,
+    #[msg("recent_blockhash is not present in SlotHashes or is too old")]
+    StaleBlockhash,
+    #[msg("Recomputed commitment does not match the stored commitment")]
+    CommitmentMismatch,
+    #[msg("Reveal submitted before the minimum delay since commit has elapsed")]
+    RevealTooEarly,
+    #[msg("sample_count must be greater than zero and no more than total_chunks or MAX_SAMPLE_COUNT")]
+    InvalidSampleCount,
+    #[msg("Number of chunks/proofs supplied does not match the challenge's sample_count, or a chunk is not STORAGE_CHUNK_SIZE bytes")]
+    SampleLengthMismatch,
+    #[msg("A sampled chunk's Merkle inclusion proof does not verify against data_root")]
+    InvalidMerkleProof,
+    #[msg("This instruction requires a challenge created with create_challenge_compressed")]
+    ChallengeNotCompressed,
+    #[msg("This instruction does not support challenges created with create_challenge_compressed")]
+    ChallengeIsCompressed,
+    #[msg("The supplied merkle_tree account does not match the challenge's recorded tree")]
+    MerkleTreeMismatch,
 }
 
 // ─── Context structs ──────────────────────────────────────────────────────────
@@ -233,6 +820,9 @@ pub struct CreateChallenge<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
+    /// CHECK: address-constrained to the SlotHashes sysvar; scanned directly by `verify_recent_blockhash`.
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
 }
 
 /// Submit a solution to an existing challenge.
@@ -253,4 +843,141 @@ pub struct SubmitChallengeSolution<'info> {
     #[account(mut)]
     pub miner: Signer<'info>,
     pub system_program: Program<'info, System>,
+    /// CHECK: address-constrained to the SlotHashes sysvar. Not read here — `challenge.recent_blockhash`
+    /// was already validated against it in `create_challenge` — but required in the instruction so
+    /// clients and indexers can rely on it always being part of the anchoring/submission pair.
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+/// Commit phase of the commit–reveal flow. The `solution_commitment` PDA is unique per
+/// (challenge, miner) — a miner can only have one live commitment per challenge at a time.
+#[derive(Accounts)]
+pub struct CommitSolution<'info> {
+    pub challenge: Account<'info, PowChallenge>,
+    #[account(
+        init,
+        payer = miner,
+        space = SOLUTION_COMMITMENT_SPACE,
+        seeds = [b"commitment", challenge.key().as_ref(), miner.key().as_ref()],
+        bump,
+    )]
+    pub solution_commitment: Account<'info, SolutionCommitment>,
+    #[account(mut)]
+    pub miner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Reveal phase of the commit–reveal flow. Closes `solution_commitment` (refunding rent to
+/// the miner) and creates the `solution_record` PDA exactly like `submit_challenge_solution`.
+#[derive(Accounts)]
+#[instruction(nonce: u64, salt: [u8; 32])]
+pub struct RevealSolution<'info> {
+    #[account(mut)]
+    pub challenge: Account<'info, PowChallenge>,
+    #[account(
+        mut,
+        close = miner,
+        seeds = [b"commitment", challenge.key().as_ref(), miner.key().as_ref()],
+        bump,
+        has_one = challenge,
+    )]
+    pub solution_commitment: Account<'info, SolutionCommitment>,
+    #[account(
+        init,
+        payer = miner,
+        space = SOLUTION_RECORD_SPACE,
+        seeds = [b"solution", challenge.key().as_ref(), miner.key().as_ref()],
+        bump,
+    )]
+    pub solution_record: Account<'info, SolutionRecord>,
+    #[account(mut)]
+    pub miner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Admin creates a new storage challenge. Mirrors `CreateChallenge`'s blockhash-anchoring.
+#[derive(Accounts)]
+pub struct CreateStorageChallenge<'info> {
+    #[account(init, payer = authority, space = STORAGE_CHALLENGE_SPACE)]
+    pub challenge: Account<'info, StorageChallenge>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: address-constrained to the SlotHashes sysvar; scanned directly by `verify_recent_blockhash`.
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+/// Submit a Proof-of-Replication proof for an existing storage challenge.
+/// The `storage_proof_record` PDA is unique per (challenge, miner) — prevents replay.
+#[derive(Accounts)]
+pub struct SubmitStorageProof<'info> {
+    #[account(mut)]
+    pub challenge: Account<'info, StorageChallenge>,
+    #[account(
+        init,
+        payer = miner,
+        space = STORAGE_PROOF_RECORD_SPACE,
+        seeds = [b"storage-proof", challenge.key().as_ref(), miner.key().as_ref()],
+        bump,
+    )]
+    pub storage_proof_record: Account<'info, StorageProofRecord>,
+    #[account(mut)]
+    pub miner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a new PoW challenge backed by a `spl-account-compression` concurrent Merkle tree.
+/// `merkle_tree` must be pre-allocated by the caller (sized for `max_depth`/`max_buffer_size`,
+/// per `spl-account-compression`'s own convention); `tree_authority` is a program-derived
+/// signer so the program — not the challenge's admin — can append leaves on every submission.
+#[derive(Accounts)]
+pub struct CreateChallengeCompressed<'info> {
+    #[account(init, payer = authority, space = POW_CHALLENGE_SPACE)]
+    pub challenge: Account<'info, PowChallenge>,
+    /// CHECK: validated and initialized by the compression program via CPI.
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: PDA signer for CPIs against `merkle_tree`; owns no data of its own.
+    #[account(seeds = [merkle_tree.key().as_ref()], bump)]
+    pub tree_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: address-constrained to the SlotHashes sysvar; scanned directly by `verify_recent_blockhash`.
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
+}
+
+/// Submit a solution to a compressed challenge. No `SolutionRecord` PDA is created — the
+/// solution is appended as a leaf to `merkle_tree` instead, via the same program-derived
+/// `tree_authority` signer used at creation. `solution_marker` is unique per (challenge,
+/// miner) via `init` and provides the replay protection `append` itself doesn't, at the cost
+/// of still allocating one small rent-paying account per miner (see its doc comment).
+#[derive(Accounts)]
+pub struct SubmitChallengeSolutionCompressed<'info> {
+    #[account(mut)]
+    pub challenge: Account<'info, PowChallenge>,
+    /// CHECK: validated against `challenge.merkle_tree`; modified by the compression program via CPI.
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: PDA signer for CPIs against `merkle_tree`; owns no data of its own.
+    #[account(seeds = [merkle_tree.key().as_ref()], bump)]
+    pub tree_authority: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = miner,
+        space = COMPRESSED_SOLUTION_MARKER_SPACE,
+        seeds = [b"compressed-solution", challenge.key().as_ref(), miner.key().as_ref()],
+        bump,
+    )]
+    pub solution_marker: Account<'info, CompressedSolutionMarker>,
+    #[account(mut)]
+    pub miner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub log_wrapper: Program<'info, Noop>,
 }